@@ -1,11 +1,16 @@
+use std::any::TypeId;
 use std::path::Path;
 
 use bevy::prelude::*;
 use bevy::ecs::all_tuples;
 use bevy::ecs::system::SystemState;
 use bevy::ecs::component::ComponentId;
+use bevy::ecs::entity::EntityMap;
 use bevy::ecs::query::ReadOnlyWorldQuery;
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::reflect::TypeRegistryInternal;
 use bevy::scene::DynamicEntity;
+use bevy::scene::serde::SceneSerializer;
 use bevy::utils::{HashMap, HashSet};
 
 use thiserror::Error;
@@ -15,10 +20,55 @@ use thiserror::Error;
 pub enum SceneExportError {
     #[error("Bevy Scene serialization to RON format failed")]
     Ron(#[from] ron::Error),
+    #[error("Bevy Scene serialization to binary format failed")]
+    Bincode(#[from] bincode::Error),
     #[error("Error writing to output file")]
     Io(#[from] std::io::Error),
 }
 
+/// Selects the byte encoding used when serializing a [`DynamicScene`]
+///
+/// See [`SceneSerialize::serialize`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SceneOutputFormat {
+    /// Human-readable RON, pretty-printed. This is the default, and is what
+    /// Bevy itself uses for `.scn.ron` assets.
+    #[default]
+    RonPretty,
+    /// RON, printed without extra whitespace. Produces smaller files and
+    /// smaller diffs than [`SceneOutputFormat::RonPretty`], at the cost of
+    /// being harder to read.
+    RonCompact,
+    /// Bevy's binary scene encoding. Smaller and faster to load than either
+    /// RON variant, but not human-readable.
+    Binary,
+}
+
+/// Extension trait adding format-aware byte serialization to [`DynamicScene`]
+///
+/// Unlike `DynamicScene::serialize_ron`, this lets you pick compact RON or
+/// binary output, and hands back a plain byte buffer rather than a `String`,
+/// so the result can be written anywhere, not just to a `.scn.ron` file.
+pub trait SceneSerialize {
+    fn serialize(&self, type_registry: &AppTypeRegistry, format: SceneOutputFormat) -> Result<Vec<u8>, SceneExportError>;
+}
+
+impl SceneSerialize for DynamicScene {
+    fn serialize(&self, type_registry: &AppTypeRegistry, format: SceneOutputFormat) -> Result<Vec<u8>, SceneExportError> {
+        match format {
+            SceneOutputFormat::RonPretty => Ok(self.serialize_ron(type_registry)?.into_bytes()),
+            SceneOutputFormat::RonCompact => {
+                let serializer = SceneSerializer::new(self, type_registry);
+                Ok(ron::ser::to_string(&serializer)?.into_bytes())
+            },
+            SceneOutputFormat::Binary => {
+                let serializer = SceneSerializer::new(self, type_registry);
+                Ok(bincode::serialize(&serializer)?)
+            },
+        }
+    }
+}
+
 /// Create a Bevy Dynamic Scene with specific entities and components.
 ///
 /// The two generic parameters are treated the same way as with Bevy `Query`.
@@ -64,13 +114,14 @@ where
             .collect();
 
         DynamicEntity {
-            entity: entity.index(),
+            entity,
             components,
         }
     }).collect();
 
     DynamicScene {
         entities,
+        resources: Default::default(),
     }
 }
 
@@ -78,6 +129,11 @@ where
 ///
 /// Creates a file in the Bevy Scene RON format. Path should end in `.scn.ron`.
 ///
+/// This function intentionally does not take a [`SceneOutputFormat`]
+/// parameter, so that it remains source-compatible with existing callers.
+/// If you need a different format, use [`SceneBuilder`] and its
+/// `.with_output_format()` instead.
+///
 /// On success (if both scene generation and file output succeed), will return
 /// the generated [`DynamicScene`], just in case you need it.
 pub fn scene_file_from_query_components<Q, F>(
@@ -91,7 +147,7 @@ where
     let scene = scene_from_query_components::<Q, F>(world);
     let type_registry = world.get_resource::<AppTypeRegistry>()
         .expect("The World provided for scene generation does not contain a TypeRegistry");
-    let data = scene.serialize_ron(type_registry)?;
+    let data = scene.serialize(type_registry, SceneOutputFormat::RonPretty)?;
     std::fs::write(path, &data)?;
     Ok(scene)
 }
@@ -156,13 +212,14 @@ where
             .collect();
 
         DynamicEntity {
-            entity: entity.index(),
+            entity,
             components,
         }
     }).collect();
 
     DynamicScene {
         entities,
+        resources: Default::default(),
     }
 }
 
@@ -170,6 +227,11 @@ where
 ///
 /// Creates a file in the Bevy Scene RON format. Path should end in `.scn.ron`.
 ///
+/// This function intentionally does not take a [`SceneOutputFormat`]
+/// parameter, so that it remains source-compatible with existing callers.
+/// If you need a different format, use [`SceneBuilder`] and its
+/// `.with_output_format()` instead.
+///
 /// On success (if both scene generation and file output succeed), will return
 /// the generated [`DynamicScene`], just in case you need it.
 pub fn scene_file_from_query_filter<F>(
@@ -182,7 +244,7 @@ where
     let scene = scene_from_query_filter::<F>(world);
     let type_registry = world.get_resource::<AppTypeRegistry>()
         .expect("The World provided for scene generation does not contain a TypeRegistry");
-    let data = scene.serialize_ron(type_registry)?;
+    let data = scene.serialize(type_registry, SceneOutputFormat::RonPretty)?;
     std::fs::write(path, &data)?;
     Ok(scene)
 }
@@ -202,11 +264,170 @@ where
     assets.add(scene)
 }
 
+/// Create a Bevy Dynamic Scene with specific resources.
+///
+/// The created scene will have no entities, and will contain only the
+/// resources listed in `L`, reflected from the provided `world`.
+///
+/// If what you need cannot be expressed with just a resource list,
+/// try [`SceneBuilder`].
+pub fn scene_from_resources<L>(
+    world: &mut World,
+) -> DynamicScene
+where
+    L: ResourceList,
+{
+    let type_registry = world.get_resource::<AppTypeRegistry>()
+        .expect("The World provided for scene generation does not contain a TypeRegistry")
+        .read();
+
+    let mut ids = HashSet::new();
+    L::do_component_ids(world, &mut |id| {ids.insert(id);});
+
+    let get_reflect_resource_by_id = |id: ComponentId|
+        world.components()
+            .get_info(id)
+            .and_then(|info| info.type_id())
+            .and_then(|type_id| type_registry.get(type_id))
+            .and_then(|reg| reg.data::<ReflectResource>())
+            .and_then(|rr| rr.reflect(world))
+            .map(|r| r.clone_value());
+
+    let resources = ids.into_iter()
+        .filter_map(get_reflect_resource_by_id)
+        .collect();
+
+    DynamicScene {
+        entities: Default::default(),
+        resources,
+    }
+}
+
+/// Convenience wrapper for [`scene_from_resources`] to output to file
+///
+/// Creates a file in the Bevy Scene RON format. Path should end in `.scn.ron`.
+///
+/// This function does not take a [`SceneOutputFormat`] parameter; it always
+/// writes pretty RON. If you need a different format, use [`SceneBuilder`]
+/// (with `.add_resources()`) and its `.with_output_format()` instead.
+///
+/// On success (if both scene generation and file output succeed), will return
+/// the generated [`DynamicScene`], just in case you need it.
+pub fn scene_file_from_resources<L>(
+    world: &mut World,
+    path: impl AsRef<Path>,
+) -> Result<DynamicScene, SceneExportError>
+where
+    L: ResourceList,
+{
+    let scene = scene_from_resources::<L>(world);
+    let type_registry = world.get_resource::<AppTypeRegistry>()
+        .expect("The World provided for scene generation does not contain a TypeRegistry");
+    let data = scene.serialize(type_registry, SceneOutputFormat::RonPretty)?;
+    std::fs::write(path, &data)?;
+    Ok(scene)
+}
+
 enum ComponentSelection {
     All,
     ByIds(HashSet<ComponentId>),
 }
 
+/// Rewrite entity references inside already-reflected components so that
+/// they point at the remapped scene-local indices rather than the original
+/// `World` indices.
+///
+/// This uses a scratch `World` together with each type's registered
+/// `ReflectMapEntities`, the same mechanism Bevy itself uses when
+/// instantiating a scene, just run in reverse: the (already exported)
+/// component data is spawned into the scratch world under the new indices,
+/// `ReflectMapEntities` is allowed to remap any entity references it finds,
+/// and the remapped values are read back out.
+///
+/// A reference can point at an entity that wasn't part of the export at all
+/// (e.g. a child added without its parent). There is no scene-local slot to
+/// remap such a reference to, so rather than let `ReflectMapEntities` invent
+/// one via `EntityMap::get_or_reserve` (which would produce a scene index
+/// disconnected from the `0..N` space assigned to the exported entities),
+/// every non-exported entity still alive in `world` is pre-mapped to itself.
+/// This leaves references to entities outside the selection pointing at
+/// their original world index, unchanged, instead of a meaningless one.
+fn remap_component_entities(
+    world: &World,
+    entities: &mut [DynamicEntity],
+    remap: &HashMap<Entity, Entity>,
+    type_registry: &TypeRegistryInternal,
+) {
+    let original_by_scene_index: HashMap<Entity, Entity> = remap.iter()
+        .map(|(entity, scene_entity)| (*scene_entity, *entity))
+        .collect();
+
+    let mut scratch = World::new();
+    let mut entity_map = EntityMap::default();
+
+    for entity_ref in world.iter_entities() {
+        let entity = entity_ref.id();
+        if !remap.contains_key(&entity) {
+            entity_map.insert(entity, entity);
+        }
+    }
+
+    for dynamic_entity in entities.iter() {
+        let original = original_by_scene_index[&dynamic_entity.entity];
+        let scratch_entity = scratch.spawn_empty().id();
+        entity_map.insert(original, scratch_entity);
+        for component in &dynamic_entity.components {
+            if let Some(reflect_component) = type_registry
+                .get_with_name(component.type_name())
+                .and_then(|reg| reg.data::<ReflectComponent>())
+            {
+                reflect_component.apply_or_insert(&mut scratch.entity_mut(scratch_entity), &**component);
+            }
+        }
+    }
+
+    for registration in type_registry.iter() {
+        if let Some(map_entities) = registration.data::<ReflectMapEntities>() {
+            map_entities.map_all_entities(&mut scratch, &mut entity_map);
+        }
+    }
+
+    for dynamic_entity in entities.iter_mut() {
+        let original = original_by_scene_index[&dynamic_entity.entity];
+        let scratch_entity = entity_map.get(original)
+            .expect("entity was just inserted into the map");
+        for component in dynamic_entity.components.iter_mut() {
+            if let Some(reflect_component) = type_registry
+                .get_with_name(component.type_name())
+                .and_then(|reg| reg.data::<ReflectComponent>())
+            {
+                if let Some(updated) = reflect_component.reflect(scratch.entity(scratch_entity)) {
+                    *component = updated.clone_value();
+                }
+            }
+        }
+    }
+}
+
+/// A global filter applied to entities added in "all components" mode
+///
+/// An allow-list (`Allowlist`) means only the listed component types are
+/// ever extracted. A deny-list (`Denylist`) means every component is
+/// extracted except the listed ones. The two modes are mutually exclusive:
+/// switching to one clears whatever was accumulated in the other.
+///
+/// The default is an empty deny-list, i.e. no filtering at all.
+enum Filter {
+    Allowlist(HashSet<ComponentId>),
+    Denylist(HashSet<ComponentId>),
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter::Denylist(Default::default())
+    }
+}
+
 /// Flexible tool for creating Bevy scenes
 ///
 /// You can select what entities from your `World` you would like
@@ -223,7 +444,10 @@ enum ComponentSelection {
 pub struct SceneBuilder<'w> {
     world: &'w mut World,
     ec: HashMap<Entity, ComponentSelection>,
-    ignored: HashSet<ComponentId>,
+    filter: Filter,
+    resources: HashSet<ComponentId>,
+    remap_entities: bool,
+    output_format: SceneOutputFormat,
 }
 
 impl<'w> SceneBuilder<'w> {
@@ -235,11 +459,71 @@ impl<'w> SceneBuilder<'w> {
         SceneBuilder {
             world,
             ec: Default::default(),
-            ignored: Default::default(),
+            filter: Default::default(),
+            resources: Default::default(),
+            remap_entities: false,
+            output_format: Default::default(),
         }
     }
 
-    /// Add components to the set of components to be ignored
+    /// Set the format used to serialize the scene
+    ///
+    /// Defaults to `SceneOutputFormat::RonPretty`. Affects
+    /// [`export_to_file`] and [`serialize`].
+    pub fn with_output_format(&mut self, format: SceneOutputFormat) -> &mut Self {
+        self.output_format = format;
+        self
+    }
+
+    /// Enable entity remapping
+    ///
+    /// By default, the scene's `DynamicEntity::entity` is the world entity
+    /// itself (index and generation), so the scene's entity ids are the
+    /// source world's own ids, whatever range they happen to occupy.
+    ///
+    /// With this enabled, `.build_scene()` instead assigns fresh, stable,
+    /// contiguous scene-local indices to every selected entity, and rewrites
+    /// any exported component implementing `ReflectMapEntities` (such as
+    /// `Parent`/`Children`) so that entity references point at the remapped
+    /// indices instead of the original world indices.
+    pub fn with_entity_remapping(&mut self) -> &mut Self {
+        self.remap_entities = true;
+        self
+    }
+
+    /// Add a resource to be included in the scene
+    ///
+    /// The resource will be reflected from the `World` and added to the
+    /// scene's `resources`, alongside whatever entities were selected.
+    ///
+    /// If you want to add more than one resource at a time, try:
+    ///  - [`add_resources`]
+    pub fn add_resource<R>(&mut self) -> &mut Self
+    where
+        R: Resource + Reflect,
+    {
+        if let Some(id) = self.world.components().get_resource_id(TypeId::of::<R>()) {
+            self.resources.insert(id);
+        }
+        self
+    }
+
+    /// Add resources to be included in the scene
+    ///
+    /// The resources will be reflected from the `World` and added to the
+    /// scene's `resources`, alongside whatever entities were selected.
+    ///
+    /// If you only want to add a single resource, try:
+    ///  - [`add_resource`]
+    pub fn add_resources<L>(&mut self) -> &mut Self
+    where
+        L: ResourceList,
+    {
+        L::do_component_ids(self.world, &mut |id| {self.resources.insert(id);});
+        self
+    }
+
+    /// Add components to the set of components to be ignored (deny-list)
     ///
     /// This applies only to entities without explicit component selections.
     ///
@@ -248,11 +532,52 @@ impl<'w> SceneBuilder<'w> {
     ///
     /// If an entity was added in "all components" mode, then `.build_scene()`
     /// will skip any of these components that it encounters.
+    ///
+    /// This switches the builder to deny-list mode, if it was previously in
+    /// allow-list mode (added via [`allow_components`]), clearing whatever
+    /// was allowed.
     pub fn ignore_components<Q>(&mut self) -> &mut Self
     where
         Q: ComponentList,
     {
-        Q::do_component_ids(self.world, &mut |id| {self.ignored.insert(id);});
+        let ids = match &mut self.filter {
+            Filter::Denylist(ids) => ids,
+            Filter::Allowlist(_) => {
+                self.filter = Filter::Denylist(Default::default());
+                let Filter::Denylist(ids) = &mut self.filter else { unreachable!() };
+                ids
+            }
+        };
+        Q::do_component_ids(self.world, &mut |id| {ids.insert(id);});
+        self
+    }
+
+    /// Add components to the set of components that are allowed (allow-list)
+    ///
+    /// This applies only to entities without explicit component selections.
+    ///
+    /// If you have explicitly added any components to specific entities, they
+    /// will still be exported to the scene, regardless of this allow-list.
+    ///
+    /// If an entity was added in "all components" mode, then `.build_scene()`
+    /// will only include the components listed here.
+    ///
+    /// This switches the builder to allow-list mode, if it was previously in
+    /// deny-list mode (added via [`ignore_components`]), clearing whatever
+    /// was ignored.
+    pub fn allow_components<Q>(&mut self) -> &mut Self
+    where
+        Q: ComponentList,
+    {
+        let ids = match &mut self.filter {
+            Filter::Allowlist(ids) => ids,
+            Filter::Denylist(_) => {
+                self.filter = Filter::Allowlist(Default::default());
+                let Filter::Allowlist(ids) = &mut self.filter else { unreachable!() };
+                ids
+            }
+        };
+        Q::do_component_ids(self.world, &mut |id| {ids.insert(id);});
         self
     }
 
@@ -277,6 +602,29 @@ impl<'w> SceneBuilder<'w> {
         self
     }
 
+    /// Add all entities that match the given query filter, together with all of their descendants
+    ///
+    /// This is the hierarchy-aware counterpart of [`add_from_query_filter`]:
+    /// every entity matched by the query filter is treated as a root and
+    /// expanded with [`add_entity_hierarchy`], including its rule that
+    /// existing narrower selections on a descendant are left alone rather
+    /// than being widened.
+    ///
+    /// If you don't want to include descendants, try:
+    ///  - [`add_from_query_filter`]
+    pub fn add_hierarchies_from_query_filter<F>(&mut self) -> &mut Self
+    where
+        F: ReadOnlyWorldQuery + 'static,
+    {
+        let mut ss = SystemState::<Query<Entity, F>>::new(self.world);
+        let q = ss.get(self.world);
+        let roots: Vec<Entity> = q.iter().collect();
+        for root in roots {
+            self.add_entity_hierarchy(root);
+        }
+        self
+    }
+
     /// Add a specific entity
     ///
     /// The entity ID provided will be added, if it has not been already.
@@ -290,6 +638,39 @@ impl<'w> SceneBuilder<'w> {
         self
     }
 
+    /// Add a specific entity, together with all of its descendants
+    ///
+    /// Starting from `root`, this follows the `Children` component
+    /// transitively, adding `root` and every descendant it finds to the
+    /// selection, in "all components" mode. Guards against cycles, in case
+    /// the hierarchy is malformed.
+    ///
+    /// This is what you want when exporting things like characters, where
+    /// the root entity has child entities for bones/attachments/etc, and
+    /// leaving any of them out of the scene would break the hierarchy.
+    ///
+    /// If an entity found while walking the hierarchy already has a narrower
+    /// selection (added via [`add_components_to_entity`] or
+    /// [`add_components_to_entities`]), that selection is left alone rather
+    /// than being widened to "all components".
+    ///
+    /// If you only want the entity itself, try:
+    ///  - [`add_entity`]
+    pub fn add_entity_hierarchy(&mut self, root: Entity) -> &mut Self {
+        let mut visited = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(e) = stack.pop() {
+            if !visited.insert(e) {
+                continue;
+            }
+            self.ec.entry(e).or_insert(ComponentSelection::All);
+            if let Some(children) = self.world.get::<Children>(e) {
+                stack.extend(children.iter().copied());
+            }
+        }
+        self
+    }
+
     /// Include the specified components on a given entity ID
     ///
     /// The entity ID provided will be added, if it has not been already.
@@ -401,7 +782,14 @@ impl<'w> SceneBuilder<'w> {
             .expect("The World provided to the SceneBuilder does not contain a TypeRegistry")
             .read();
 
-        let entities = self.ec.iter().map(|(entity, csel)| {
+        let remap: Option<HashMap<Entity, Entity>> = self.remap_entities.then(|| {
+            self.ec.keys()
+                .enumerate()
+                .map(|(index, entity)| (*entity, Entity::from_raw(index as u32)))
+                .collect()
+        });
+
+        let mut entities: Vec<DynamicEntity> = self.ec.iter().map(|(entity, csel)| {
             let get_reflect_by_id = |id|
                 self.world.components()
                     .get_info(id)
@@ -417,7 +805,10 @@ impl<'w> SceneBuilder<'w> {
                         .and_then(|eloc| self.world.archetypes().get(eloc.archetype_id))
                         .into_iter()
                         .flat_map(|a| a.components())
-                        .filter(|id| !self.ignored.contains(&id))
+                        .filter(|id| match &self.filter {
+                            Filter::Denylist(ids) => !ids.contains(id),
+                            Filter::Allowlist(ids) => ids.contains(id),
+                        })
                         .filter_map(get_reflect_by_id)
                         .collect()
                 },
@@ -429,20 +820,45 @@ impl<'w> SceneBuilder<'w> {
                 },
             };
 
+            let scene_index = remap.as_ref()
+                .map(|remap| remap[entity])
+                .unwrap_or(*entity);
+
             DynamicEntity {
-                entity: entity.index(),
+                entity: scene_index,
                 components,
             }
         }).collect();
 
+        if let Some(remap) = &remap {
+            remap_component_entities(self.world, &mut entities, remap, &type_registry);
+        }
+
+        let get_reflect_resource_by_id = |id: ComponentId|
+            self.world.components()
+                .get_info(id)
+                .and_then(|info| info.type_id())
+                .and_then(|type_id| type_registry.get(type_id))
+                .and_then(|reg| reg.data::<ReflectResource>())
+                .and_then(|rr| rr.reflect(self.world))
+                .map(|r| r.clone_value());
+
+        let resources = self.resources.iter()
+            .cloned()
+            .filter_map(get_reflect_resource_by_id)
+            .collect();
+
         DynamicScene {
             entities,
+            resources,
         }
     }
 
     /// Convenience method: build the scene and serialize to file
     ///
-    /// Creates a file in the Bevy Scene RON format. Path should end in `.scn.ron`.
+    /// Creates a file in this builder's configured [`SceneOutputFormat`]
+    /// (see [`with_output_format`]). If you are using the default
+    /// `SceneOutputFormat::RonPretty`, the path should end in `.scn.ron`.
     ///
     /// On success (if both scene generation and file output succeed), will return
     /// the generated [`DynamicScene`], just in case you need it.
@@ -450,11 +866,24 @@ impl<'w> SceneBuilder<'w> {
         let scene = self.build_scene();
         let type_registry = self.world.get_resource::<AppTypeRegistry>()
             .expect("The World provided to the SceneBuilder does not contain a TypeRegistry");
-        let data = scene.serialize_ron(type_registry)?;
+        let data = scene.serialize(type_registry, self.output_format)?;
         std::fs::write(path, &data)?;
         Ok(scene)
     }
 
+    /// Convenience method: build the scene and serialize it to bytes
+    ///
+    /// Like [`export_to_file`], but hands back the serialized bytes (in this
+    /// builder's configured [`SceneOutputFormat`]) instead of writing them to
+    /// a file, for when you want to put the scene in an archive, send it over
+    /// a socket, or otherwise handle the bytes yourself.
+    pub fn serialize(&self) -> Result<Vec<u8>, SceneExportError> {
+        let scene = self.build_scene();
+        let type_registry = self.world.get_resource::<AppTypeRegistry>()
+            .expect("The World provided to the SceneBuilder does not contain a TypeRegistry");
+        scene.serialize(type_registry, self.output_format)
+    }
+
     /// Convenience method: build the scene and add to the app's asset collection
     ///
     /// Returns an asset handle that can be used for spawning the scene, (with [`DynamicSceneBundle`]).
@@ -510,6 +939,236 @@ macro_rules! componentlist_impl {
 
 all_tuples!(componentlist_impl, 0, 15, T);
 
+/// Represents a selection of resources to export into a scene.
+///
+/// Works similarly to [`ComponentList`], but for `World` resources
+/// instead of entity components. Implemented for `&R` where `R` is
+/// a `Resource + Reflect` type, and for tuples of such references.
+pub trait ResourceList {
+    fn do_component_ids<F: FnMut(ComponentId)>(world: &World, f: &mut F);
+}
+
+impl<R: Resource + Reflect> ResourceList for &R {
+    #[inline]
+    fn do_component_ids<F: FnMut(ComponentId)>(world: &World, f: &mut F) {
+        if let Some(id) = world.components().get_resource_id(TypeId::of::<R>()) {
+            f(id);
+        }
+    }
+}
+
+macro_rules! resourcelist_impl {
+    ($($x:ident),*) => {
+        impl<$($x: ResourceList),*> ResourceList for ($($x,)*) {
+            #[inline]
+            fn do_component_ids<F: FnMut(ComponentId)>(_world: &World, _f: &mut F) {
+                $($x::do_component_ids(_world, _f);)*
+            }
+        }
+    };
+}
+
+all_tuples!(resourcelist_impl, 0, 15, R);
+
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct A;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct B;
+
+    #[derive(Resource, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct MyResource {
+        value: i32,
+    }
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_component::<A>();
+        world.init_component::<B>();
+        {
+            let registry = world.resource::<AppTypeRegistry>().clone();
+            let mut registry = registry.write();
+            registry.register::<A>();
+            registry.register::<B>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+            registry.register::<MyResource>();
+        }
+        world
+    }
+
+    #[test]
+    fn ignore_components_switches_back_to_denylist_mode() {
+        let mut world = test_world();
+        let mut builder = SceneBuilder::new(&mut world);
+
+        builder.allow_components::<&A>();
+        assert!(matches!(builder.filter, Filter::Allowlist(_)));
+
+        builder.ignore_components::<&B>();
+        match &builder.filter {
+            Filter::Denylist(ids) => assert_eq!(ids.len(), 1),
+            Filter::Allowlist(_) => panic!(
+                "ignore_components should switch back to deny-list mode, not merge into the allow-list"
+            ),
+        }
+    }
+
+    #[test]
+    fn allow_components_switches_back_to_allowlist_mode() {
+        let mut world = test_world();
+        let mut builder = SceneBuilder::new(&mut world);
+
+        builder.ignore_components::<&A>();
+        assert!(matches!(builder.filter, Filter::Denylist(_)));
+
+        builder.allow_components::<&B>();
+        match &builder.filter {
+            Filter::Allowlist(ids) => assert_eq!(ids.len(), 1),
+            Filter::Denylist(_) => panic!(
+                "allow_components should switch back to allow-list mode, not merge into the deny-list"
+            ),
+        }
+    }
+
+    #[test]
+    fn entity_remapping_rewrites_parent_reference() {
+        let mut world = test_world();
+
+        // Bump the world's entity indices up before spawning `parent`/`child`,
+        // so their world indices can never coincide with the low,
+        // from-scratch scene-local indices `with_entity_remapping` assigns
+        // below. Without this, which of the two entities lands on scene
+        // index 0 is down to `HashMap` iteration order, and on an unlucky
+        // run a world index could collide with its own remapped scene
+        // index, making a real remap indistinguishable from a no-op.
+        // (The spawned entities are kept alive rather than despawned, since
+        // despawning would free their indices for immediate reuse.)
+        let _padding: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+
+        let child = world.spawn_empty().id();
+        let parent = world.spawn_empty().id();
+        world.entity_mut(parent).push_children(&[child]);
+
+        let mut builder = SceneBuilder::new(&mut world);
+        builder.add_entity_hierarchy(parent);
+        builder.with_entity_remapping();
+        let scene = builder.build_scene();
+
+        let parent_scene_entity = scene.entities.iter()
+            .find(|e| e.components.iter().any(|c| c.type_name() == std::any::type_name::<Children>()))
+            .expect("the exported scene should contain the parent entity")
+            .entity;
+
+        let child_scene_entity = scene.entities.iter()
+            .find(|e| e.components.iter().any(|c| c.type_name() == std::any::type_name::<Parent>()))
+            .expect("the exported scene should contain the child entity");
+
+        let reflected_parent = child_scene_entity.components.iter()
+            .find_map(|c| Parent::from_reflect(c.as_ref()))
+            .expect("the child entity should export a Parent component");
+
+        assert_ne!(
+            reflected_parent.get(), parent,
+            "the remapped Parent reference should not still point at the original world entity"
+        );
+        assert_eq!(
+            reflected_parent.get(), parent_scene_entity,
+            "the remapped Parent reference should point at the parent's new scene-local entity"
+        );
+    }
+
+    #[test]
+    fn entity_remapping_leaves_out_of_selection_parent_unchanged() {
+        let mut world = test_world();
+
+        let child = world.spawn_empty().id();
+        let parent = world.spawn_empty().id();
+        world.entity_mut(parent).push_children(&[child]);
+
+        // Only the child is exported; its parent is not part of the selection.
+        let mut builder = SceneBuilder::new(&mut world);
+        builder.add_entity(child);
+        builder.with_entity_remapping();
+        let scene = builder.build_scene();
+
+        assert_eq!(scene.entities.len(), 1);
+        let reflected_parent = scene.entities[0].components.iter()
+            .find_map(|c| Parent::from_reflect(c.as_ref()))
+            .expect("the child entity should export a Parent component");
+
+        assert_eq!(
+            reflected_parent.get(), parent,
+            "a reference to an entity outside the export selection should keep its original world entity, unchanged"
+        );
+    }
+
+    #[test]
+    fn resource_round_trips_through_build_scene() {
+        let mut world = test_world();
+        world.insert_resource(MyResource { value: 42 });
+
+        let mut builder = SceneBuilder::new(&mut world);
+        builder.add_resource::<MyResource>();
+        let scene = builder.build_scene();
+
+        assert_eq!(scene.resources.len(), 1);
+        let reflected = scene.resources.iter()
+            .find_map(|r| MyResource::from_reflect(r.as_ref()))
+            .expect("the scene should export the MyResource resource");
+
+        assert_eq!(reflected, MyResource { value: 42 });
+    }
+
+    #[test]
+    fn add_entity_hierarchy_terminates_on_a_cycle() {
+        let mut world = test_world();
+
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        // `push_children` doesn't itself guard against cycles, so this
+        // produces a genuinely malformed hierarchy: A has Children = [B],
+        // and B has Children = [A]. `add_entity_hierarchy` must not
+        // infinite-loop walking it.
+        world.entity_mut(a).push_children(&[b]);
+        world.entity_mut(b).push_children(&[a]);
+
+        let mut builder = SceneBuilder::new(&mut world);
+        builder.add_entity_hierarchy(a);
+        let scene = builder.build_scene();
+
+        assert_eq!(
+            scene.entities.len(), 2,
+            "each entity in the cycle should be exported exactly once"
+        );
+    }
+
+    #[test]
+    fn add_hierarchies_from_query_filter_expands_each_matched_root() {
+        let mut world = test_world();
+
+        let child = world.spawn_empty().id();
+        let parent = world.spawn(A).id();
+        world.entity_mut(parent).push_children(&[child]);
+
+        let unrelated = world.spawn_empty().id();
+
+        let mut builder = SceneBuilder::new(&mut world);
+        builder.add_hierarchies_from_query_filter::<With<A>>();
+        let scene = builder.build_scene();
+
+        let exported: HashSet<Entity> = scene.entities.iter().map(|e| e.entity).collect();
+        assert_eq!(exported.len(), 2, "both the matched root and its child should be exported");
+        assert!(exported.contains(&parent));
+        assert!(exported.contains(&child));
+        assert!(!exported.contains(&unrelated));
+    }
 }